@@ -0,0 +1,115 @@
+use algebra::{groups::Group, UniformRand};
+use core::marker::PhantomData;
+use rand::Rng;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::crh::pedersen::{bytes_to_bits, Parameters, Window};
+use crate::crh::{CRHError, FixedLengthCRH, TwoToOneCRH};
+use crate::Error;
+
+/// Bowe–Hopwood's optimized Pedersen hash consumes the input in 3-bit chunks,
+/// signing the accumulated scalar, so each generator is used once per 3-bit
+/// chunk instead of once per bit.
+pub const CHUNK_SIZE: usize = 3;
+
+pub struct BoweHopwoodPedersenCRH<G: Group, W: Window> {
+    group: PhantomData<G>,
+    window: PhantomData<W>,
+}
+
+impl<G: Group, W: Window> BoweHopwoodPedersenCRH<G, W> {
+    fn create_generators<R: Rng>(rng: &mut R) -> Vec<Vec<G>> {
+        let mut generators = Vec::with_capacity(W::NUM_WINDOWS);
+        for _ in 0..W::NUM_WINDOWS {
+            let mut base = G::rand(rng);
+            let mut powers = Vec::with_capacity(W::WINDOW_SIZE);
+            for _ in 0..W::WINDOW_SIZE {
+                powers.push(base);
+                // Advance by 4-bit segments, matching the signed-digit encoding.
+                for _ in 0..4 {
+                    base.double_in_place();
+                }
+            }
+            generators.push(powers);
+        }
+        generators
+    }
+
+    fn hash_bits(parameters: &Parameters<G>, input: &[u8]) -> G {
+        let mut result = G::zero();
+        let bits = bytes_to_bits(input);
+        for (window_bits, powers) in bits.chunks(W::WINDOW_SIZE * CHUNK_SIZE).zip(&parameters.generators) {
+            for (chunk, base) in window_bits.chunks(CHUNK_SIZE).zip(powers) {
+                // Interpret each 3-bit chunk as a signed digit in {-4, .., 4}\{0}.
+                let mut encoded = *base;
+                if !chunk.is_empty() && chunk[0] {
+                    encoded += base;
+                }
+                if chunk.len() > 1 && chunk[1] {
+                    encoded += base;
+                    encoded += base;
+                }
+                if chunk.len() > 2 && chunk[2] {
+                    encoded = encoded.neg();
+                }
+                result += &encoded;
+            }
+        }
+        result
+    }
+}
+
+impl<G: Group, W: Window> FixedLengthCRH for BoweHopwoodPedersenCRH<G, W> {
+    const INPUT_SIZE_BITS: usize = W::WINDOW_SIZE * W::NUM_WINDOWS * CHUNK_SIZE;
+    type Output = G;
+    type Parameters = Parameters<G>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        Ok(Parameters {
+            generators: Self::create_generators(rng),
+        })
+    }
+
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        if input.len() * 8 > Self::INPUT_SIZE_BITS {
+            return Err(Box::new(CRHError::IncorrectInputLength(input.len())));
+        }
+        let mut padded = Vec::with_capacity(Self::INPUT_SIZE_BITS / 8);
+        padded.extend_from_slice(input);
+        padded.resize((Self::INPUT_SIZE_BITS + 7) / 8, 0u8);
+        Ok(Self::hash_bits(parameters, &padded))
+    }
+}
+
+impl<G: Group, W: Window> TwoToOneCRH for BoweHopwoodPedersenCRH<G, W> {
+    const LEFT_INPUT_SIZE_BITS: usize = Self::INPUT_SIZE_BITS / 2;
+    const RIGHT_INPUT_SIZE_BITS: usize = Self::INPUT_SIZE_BITS / 2;
+    type Output = G;
+    type Parameters = Parameters<G>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        <Self as FixedLengthCRH>::setup(rng)
+    }
+
+    fn compress(
+        parameters: &Self::Parameters,
+        left: &[u8],
+        right: &[u8],
+    ) -> Result<Self::Output, Error> {
+        if (left.len() + right.len()) * 8 > <Self as FixedLengthCRH>::INPUT_SIZE_BITS {
+            return Err(Box::new(CRHError::WindowTooSmall {
+                windows: W::NUM_WINDOWS,
+                needed: (left.len() + right.len()) * 8,
+            }));
+        }
+        let mut buffer = Vec::with_capacity(left.len() + right.len());
+        buffer.extend_from_slice(left);
+        buffer.extend_from_slice(right);
+        <Self as FixedLengthCRH>::evaluate(parameters, &buffer)
+    }
+}
+
@@ -0,0 +1,44 @@
+use algebra::Field;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+use crate::crh::{FixedLengthCRH, TwoToOneCRH};
+
+/// In-circuit counterpart of [`FixedLengthCRH`]: allocates the parameters and
+/// enforces a single-block evaluation.
+pub trait FixedLengthCRHGadget<H: FixedLengthCRH, ConstraintF: Field>: Sized {
+    type OutputGadget: EqGadget<ConstraintF>
+        + ToBytesGadget<ConstraintF>
+        + CondSelectGadget<ConstraintF>
+        + AllocGadget<H::Output, ConstraintF>
+        + Clone
+        + Sized;
+    type ParametersGadget: AllocGadget<H::Parameters, ConstraintF> + Clone;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError>;
+}
+
+/// In-circuit counterpart of [`TwoToOneCRH`]: enforces the compression of two
+/// child digests into their parent. The trait declares its own output gadget
+/// type; the Merkle-path and variable-length gadgets require it to equal the
+/// leaf hash's so the two can be chained.
+pub trait TwoToOneCRHGadget<H: TwoToOneCRH, ConstraintF: Field>: Sized {
+    type OutputGadget: EqGadget<ConstraintF>
+        + ToBytesGadget<ConstraintF>
+        + CondSelectGadget<ConstraintF>
+        + AllocGadget<H::Output, ConstraintF>
+        + Clone
+        + Sized;
+    type ParametersGadget: AllocGadget<H::Parameters, ConstraintF> + Clone;
+
+    fn check_compression_gadget<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        left: &[UInt8],
+        right: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError>;
+}
@@ -0,0 +1,84 @@
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    curves::{models::TEModelParameters, twisted_edwards_extended::GroupProjective},
+    groups::Group,
+    ProjectiveCurve,
+};
+use core::hash::Hash;
+use core::marker::PhantomData;
+use rand::Rng;
+
+use crate::crh::pedersen::{PedersenCRH, Window};
+use crate::crh::{FixedLengthCRH, TwoToOneCRH};
+use crate::Error;
+
+/// An injective post-processing step applied to a group-valued hash output so
+/// that the digest can be fed back in as the input to another hash (e.g. the
+/// `TwoToOneCRH` at the next Merkle level) without losing collision resistance.
+pub trait InjectiveMap<G: Group> {
+    type Output: ToBytes + FromBytes + Clone + Eq + Hash + Default;
+
+    fn injective_map(element: &G) -> Result<Self::Output, Error>;
+}
+
+/// Maps a twisted-Edwards point to its affine x-coordinate. This is injective
+/// over the points reachable as hash outputs and yields a single base-field
+/// element, so the compressed digest is half the size of the raw point and can
+/// itself be re-hashed. The twisted-Edwards analogue of zexe's `TECompressor`.
+pub struct TECompressor;
+
+impl<P: TEModelParameters> InjectiveMap<GroupProjective<P>> for TECompressor {
+    type Output = P::BaseField;
+
+    fn injective_map(element: &GroupProjective<P>) -> Result<Self::Output, Error> {
+        Ok(element.into_affine().x)
+    }
+}
+
+/// Wraps [`PedersenCRH`], mapping its group-element output through `I`.
+pub struct PedersenCRHCompressor<G: Group, I: InjectiveMap<G>, W: Window> {
+    _group: PhantomData<G>,
+    _map: PhantomData<I>,
+    _window: PhantomData<W>,
+}
+
+impl<G: Group, I: InjectiveMap<G>, W: Window> FixedLengthCRH
+    for PedersenCRHCompressor<G, I, W>
+{
+    const INPUT_SIZE_BITS: usize = <PedersenCRH<G, W> as FixedLengthCRH>::INPUT_SIZE_BITS;
+    type Output = I::Output;
+    type Parameters = <PedersenCRH<G, W> as FixedLengthCRH>::Parameters;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        <PedersenCRH<G, W> as FixedLengthCRH>::setup(rng)
+    }
+
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let output = <PedersenCRH<G, W> as FixedLengthCRH>::evaluate(parameters, input)?;
+        I::injective_map(&output)
+    }
+}
+
+impl<G: Group, I: InjectiveMap<G>, W: Window> TwoToOneCRH
+    for PedersenCRHCompressor<G, I, W>
+{
+    const LEFT_INPUT_SIZE_BITS: usize =
+        <PedersenCRH<G, W> as TwoToOneCRH>::LEFT_INPUT_SIZE_BITS;
+    const RIGHT_INPUT_SIZE_BITS: usize =
+        <PedersenCRH<G, W> as TwoToOneCRH>::RIGHT_INPUT_SIZE_BITS;
+    type Output = I::Output;
+    type Parameters = <PedersenCRH<G, W> as TwoToOneCRH>::Parameters;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        <PedersenCRH<G, W> as TwoToOneCRH>::setup(rng)
+    }
+
+    fn compress(
+        parameters: &Self::Parameters,
+        left: &[u8],
+        right: &[u8],
+    ) -> Result<Self::Output, Error> {
+        let output = <PedersenCRH<G, W> as TwoToOneCRH>::compress(parameters, left, right)?;
+        I::injective_map(&output)
+    }
+}
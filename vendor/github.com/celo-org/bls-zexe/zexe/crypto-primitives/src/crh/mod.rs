@@ -1,10 +1,12 @@
-use algebra::bytes::ToBytes;
+use algebra::bytes::{FromBytes, ToBytes};
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use core::hash::Hash;
 use rand::Rng;
-use std::hash::Hash;
 
 pub mod injective_map;
 pub mod pedersen;
 pub mod bowe_hopwood;
+pub mod variable_length;
 
 use crate::Error;
 
@@ -15,11 +17,62 @@ pub mod constraints;
 pub use constraints::*;
 
 
+/// Errors shared by the windowed CRH backends (`pedersen`, `bowe_hopwood`),
+/// which reject inputs of the wrong length and window sets too small to cover
+/// the input.
+#[derive(Debug)]
+pub enum CRHError {
+    /// The input, whose length in bytes is carried here, was longer than the
+    /// backend's input size. Shorter inputs are zero-padded, not rejected.
+    IncorrectInputLength(usize),
+    /// The configured number of windows cannot cover the input bit length.
+    WindowTooSmall { windows: usize, needed: usize },
+}
+
+impl Display for CRHError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            CRHError::IncorrectInputLength(len) => {
+                write!(f, "incorrect input length: {} bytes", len)
+            }
+            CRHError::WindowTooSmall { windows, needed } => write!(
+                f,
+                "window count {} cannot cover {} input bits",
+                windows, needed
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CRHError {}
+
 pub trait FixedLengthCRH {
     const INPUT_SIZE_BITS: usize;
-    type Output: ToBytes + Clone + Eq + Hash + Default;
-    type Parameters: Clone + Default;
+    type Output: ToBytes + FromBytes + Clone + Eq + Hash + Default;
+    type Parameters: ToBytes + FromBytes + Clone + Default;
 
     fn setup<R: Rng>(r: &mut R) -> Result<Self::Parameters, Error>;
     fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error>;
 }
+
+/// A collision-resistant hash that compresses two fixed-length child digests
+/// into a single `Output`. This is the shape needed by the inner nodes of a
+/// Merkle tree, where the two child hashes are folded together; the operands
+/// are concatenated into one bit string and run through the same windowed hash
+/// backing the corresponding `FixedLengthCRH`.
+pub trait TwoToOneCRH {
+    /// The maximum size in bits of the left operand.
+    const LEFT_INPUT_SIZE_BITS: usize;
+    /// The maximum size in bits of the right operand.
+    const RIGHT_INPUT_SIZE_BITS: usize;
+    type Output: ToBytes + FromBytes + Clone + Eq + Hash + Default;
+    type Parameters: ToBytes + FromBytes + Clone + Default;
+
+    fn setup<R: Rng>(r: &mut R) -> Result<Self::Parameters, Error>;
+    fn compress(
+        parameters: &Self::Parameters,
+        left: &[u8],
+        right: &[u8],
+    ) -> Result<Self::Output, Error>;
+}
@@ -0,0 +1,216 @@
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    groups::Group,
+    UniformRand,
+};
+use core::marker::PhantomData;
+use rand::Rng;
+
+#[cfg(feature = "std")]
+use std::io::{Read, Result as IoResult, Write};
+#[cfg(not(feature = "std"))]
+use algebra::io::{Read, Result as IoResult, Write};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::crh::{CRHError, FixedLengthCRH, TwoToOneCRH};
+use crate::Error;
+
+/// The windowing used by a Pedersen hash: the input is laid out as
+/// `NUM_WINDOWS` windows of `WINDOW_SIZE` bits each.
+pub trait Window: Clone {
+    const WINDOW_SIZE: usize;
+    const NUM_WINDOWS: usize;
+}
+
+/// The random generator bases of a Pedersen hash, one vector of doublings per
+/// window. This is the data a `setup()` produces and the thing we want to be
+/// able to persist and reload deterministically.
+#[derive(Clone, Default)]
+pub struct Parameters<G: Group> {
+    pub generators: Vec<Vec<G>>,
+}
+
+pub struct PedersenCRH<G: Group, W: Window> {
+    group: PhantomData<G>,
+    window: PhantomData<W>,
+}
+
+impl<G: Group, W: Window> PedersenCRH<G, W> {
+    fn create_generators<R: Rng>(rng: &mut R) -> Vec<Vec<G>> {
+        let mut generators = Vec::with_capacity(W::NUM_WINDOWS);
+        for _ in 0..W::NUM_WINDOWS {
+            let mut base = G::rand(rng);
+            let mut powers = Vec::with_capacity(W::WINDOW_SIZE);
+            for _ in 0..W::WINDOW_SIZE {
+                powers.push(base);
+                base.double_in_place();
+            }
+            generators.push(powers);
+        }
+        generators
+    }
+
+    /// Runs the windowed hash over an already-sized bit string. Shared by the
+    /// `FixedLengthCRH` leaf path and the `TwoToOneCRH` compression path.
+    fn hash_bits(parameters: &Parameters<G>, input: &[u8]) -> G {
+        let mut result = G::zero();
+        for (bits, powers) in bytes_to_bits(input)
+            .chunks(W::WINDOW_SIZE)
+            .zip(&parameters.generators)
+        {
+            for (bit, base) in bits.iter().zip(powers) {
+                if *bit {
+                    result += base;
+                }
+            }
+        }
+        result
+    }
+}
+
+impl<G: Group, W: Window> FixedLengthCRH for PedersenCRH<G, W> {
+    const INPUT_SIZE_BITS: usize = W::WINDOW_SIZE * W::NUM_WINDOWS;
+    type Output = G;
+    type Parameters = Parameters<G>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        Ok(Parameters {
+            generators: Self::create_generators(rng),
+        })
+    }
+
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        if input.len() * 8 > Self::INPUT_SIZE_BITS {
+            return Err(Box::new(CRHError::IncorrectInputLength(input.len())));
+        }
+        // Right-pad to a full window layout so the bit chunks line up.
+        let mut padded = Vec::with_capacity(Self::INPUT_SIZE_BITS / 8);
+        padded.extend_from_slice(input);
+        padded.resize(Self::INPUT_SIZE_BITS / 8, 0u8);
+        Ok(Self::hash_bits(parameters, &padded))
+    }
+}
+
+impl<G: Group, W: Window> TwoToOneCRH for PedersenCRH<G, W> {
+    const LEFT_INPUT_SIZE_BITS: usize = Self::INPUT_SIZE_BITS / 2;
+    const RIGHT_INPUT_SIZE_BITS: usize = Self::INPUT_SIZE_BITS / 2;
+    type Output = G;
+    type Parameters = Parameters<G>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        <Self as FixedLengthCRH>::setup(rng)
+    }
+
+    fn compress(
+        parameters: &Self::Parameters,
+        left: &[u8],
+        right: &[u8],
+    ) -> Result<Self::Output, Error> {
+        // The two operands are concatenated into a single bit string, so the
+        // windows must be wide enough to cover both halves at once.
+        if (left.len() + right.len()) * 8 > <Self as FixedLengthCRH>::INPUT_SIZE_BITS {
+            return Err(Box::new(CRHError::WindowTooSmall {
+                windows: W::NUM_WINDOWS,
+                needed: (left.len() + right.len()) * 8,
+            }));
+        }
+        let mut buffer = Vec::with_capacity(left.len() + right.len());
+        buffer.extend_from_slice(left);
+        buffer.extend_from_slice(right);
+        <Self as FixedLengthCRH>::evaluate(parameters, &buffer)
+    }
+}
+
+impl<G: Group> ToBytes for Parameters<G> {
+    fn write<Wr: Write>(&self, mut writer: Wr) -> IoResult<()> {
+        (self.generators.len() as u32).write(&mut writer)?;
+        for segment in &self.generators {
+            (segment.len() as u32).write(&mut writer)?;
+            for generator in segment {
+                generator.write(&mut writer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<G: Group> FromBytes for Parameters<G> {
+    fn read<Rd: Read>(mut reader: Rd) -> IoResult<Self> {
+        let num_segments = u32::read(&mut reader)? as usize;
+        let mut generators = Vec::with_capacity(num_segments);
+        for _ in 0..num_segments {
+            let len = u32::read(&mut reader)? as usize;
+            let mut segment = Vec::with_capacity(len);
+            for _ in 0..len {
+                segment.push(G::read(&mut reader)?);
+            }
+            generators.push(segment);
+        }
+        Ok(Parameters { generators })
+    }
+}
+
+pub(crate) fn bytes_to_bits(bytes: &[u8]) -> Vec<bool> {
+    let mut bits = Vec::with_capacity(bytes.len() * 8);
+    for byte in bytes {
+        for i in 0..8 {
+            bits.push((byte >> i) & 1 == 1);
+        }
+    }
+    bits
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::curves::edwards_bls12::EdwardsProjective;
+    use rand::thread_rng;
+
+    #[derive(Clone)]
+    struct TestWindow;
+    impl Window for TestWindow {
+        const WINDOW_SIZE: usize = 8;
+        const NUM_WINDOWS: usize = 32;
+    }
+
+    type TestCRH = PedersenCRH<EdwardsProjective, TestWindow>;
+
+    #[test]
+    fn parameters_round_trip() {
+        let rng = &mut thread_rng();
+        let params = TestCRH::setup(rng).unwrap();
+
+        let mut bytes = Vec::new();
+        params.write(&mut bytes).unwrap();
+        let recovered = Parameters::<EdwardsProjective>::read(&bytes[..]).unwrap();
+
+        assert_eq!(params.generators, recovered.generators);
+        // A parameter set read back from bytes must hash identically.
+        let input = [1u8; 32];
+        assert_eq!(
+            TestCRH::evaluate(&params, &input).unwrap(),
+            TestCRH::evaluate(&recovered, &input).unwrap()
+        );
+    }
+
+    #[test]
+    fn compress_matches_concatenated_evaluate() {
+        let rng = &mut thread_rng();
+        let params = <TestCRH as TwoToOneCRH>::setup(rng).unwrap();
+        let left = [0xAAu8; 16];
+        let right = [0x55u8; 16];
+
+        let mut concatenated = Vec::new();
+        concatenated.extend_from_slice(&left);
+        concatenated.extend_from_slice(&right);
+
+        assert_eq!(
+            <TestCRH as TwoToOneCRH>::compress(&params, &left, &right).unwrap(),
+            <TestCRH as FixedLengthCRH>::evaluate(&params, &concatenated).unwrap()
+        );
+    }
+}
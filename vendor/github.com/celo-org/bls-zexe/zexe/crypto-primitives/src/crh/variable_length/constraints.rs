@@ -0,0 +1,86 @@
+//! In-circuit analogue of [`VariableLengthCRH::finalize`].
+//!
+//! Like the Merkle-tree mirror, this is generic scaffolding written against the
+//! `FixedLengthCRHGadget` / `TwoToOneCRHGadget` traits; it ships no concrete
+//! backend gadget, so a caller supplies one from `r1cs_std` to instantiate the
+//! chained hash in a SNARK.
+//!
+//! [`VariableLengthCRH::finalize`]: crate::crh::variable_length::VariableLengthCRH::finalize
+
+use algebra::Field;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::crh::constraints::{FixedLengthCRHGadget, TwoToOneCRHGadget};
+use crate::crh::{FixedLengthCRH, TwoToOneCRH};
+
+/// In-circuit analogue of `VariableLengthCRH::finalize`: the caller supplies the
+/// raw message bytes and this enforces the same chunk-into-blocks, zero-pad,
+/// leaf-hash-then-fold chain, including the final big-endian length-strengthening
+/// block. The returned gadget is the digest of the whole message and matches the
+/// off-circuit `finalize` bit-for-bit.
+pub fn check_variable_length_gadget<H, C, HGadget, CGadget, ConstraintF, CS>(
+    mut cs: CS,
+    leaf_params: &HGadget::ParametersGadget,
+    two_to_one_params: &CGadget::ParametersGadget,
+    input: &[UInt8],
+) -> Result<HGadget::OutputGadget, SynthesisError>
+where
+    ConstraintF: Field,
+    H: FixedLengthCRH,
+    C: TwoToOneCRH<Output = H::Output>,
+    HGadget: FixedLengthCRHGadget<H, ConstraintF>,
+    CGadget: TwoToOneCRHGadget<C, ConstraintF, OutputGadget = HGadget::OutputGadget>,
+    CS: ConstraintSystem<ConstraintF>,
+{
+    let block_size = H::INPUT_SIZE_BITS / 8;
+    if block_size < 8 {
+        // Mirror the native `finalize`, which rejects a block too narrow to hold
+        // the 8-byte length suffix; the chain it would enforce is unsatisfiable.
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    // Build the same sequence of zero-padded blocks `finalize` folds: one per
+    // `block_size`-byte chunk of the message, followed by the length block.
+    let mut blocks: Vec<Vec<UInt8>> = input
+        .chunks(block_size)
+        .map(|chunk| {
+            let mut block = chunk.to_vec();
+            block.resize(block_size, UInt8::constant(0));
+            block
+        })
+        .collect();
+
+    let mut length_block: Vec<UInt8> = (input.len() as u64)
+        .to_be_bytes()
+        .iter()
+        .map(|b| UInt8::constant(*b))
+        .collect();
+    length_block.resize(block_size, UInt8::constant(0));
+    blocks.push(length_block);
+
+    let mut acc = HGadget::check_evaluation_gadget(
+        cs.ns(|| "hash_block_0"),
+        leaf_params,
+        &blocks[0],
+    )?;
+    for (i, block) in blocks.iter().enumerate().skip(1) {
+        let mut cs = cs.ns(|| format!("block_{}", i));
+        let digest =
+            HGadget::check_evaluation_gadget(cs.ns(|| "hash_block"), leaf_params, block)?;
+        let left = acc.to_bytes(cs.ns(|| "acc_bytes"))?;
+        let right = digest.to_bytes(cs.ns(|| "digest_bytes"))?;
+        acc = CGadget::check_compression_gadget(
+            cs.ns(|| "fold"),
+            two_to_one_params,
+            &left,
+            &right,
+        )?;
+    }
+    Ok(acc)
+}
@@ -0,0 +1,190 @@
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use core::marker::PhantomData;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::crh::{FixedLengthCRH, TwoToOneCRH};
+use crate::Error;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+#[cfg(feature = "r1cs")]
+pub use constraints::*;
+
+/// A streaming hasher built on top of a pair of fixed-length CRHs, modelled on
+/// the `update`/`finalize` shape of the RustCrypto `digest` traits. Input of
+/// arbitrary length is buffered, split into `H::INPUT_SIZE_BITS`-sized blocks,
+/// each block hashed with the leaf hash `H`, and the resulting digests folded
+/// together with the two-to-one compression `C` in a Merkle–Damgård chain.
+///
+/// This frees call sites from the manual padding and chunking otherwise
+/// required to feed a `FixedLengthCRH`, whose input must be exactly one block.
+pub struct VariableLengthCRH<H, C>
+where
+    H: FixedLengthCRH,
+    C: TwoToOneCRH<Output = H::Output>,
+{
+    leaf_params: H::Parameters,
+    two_to_one_params: C::Parameters,
+    buffer: Vec<u8>,
+    _hashes: PhantomData<(H, C)>,
+}
+
+impl<H, C> VariableLengthCRH<H, C>
+where
+    H: FixedLengthCRH,
+    C: TwoToOneCRH<Output = H::Output>,
+{
+    /// The block size in bytes. Inputs are chunked at this granularity.
+    const BLOCK_SIZE: usize = H::INPUT_SIZE_BITS / 8;
+
+    /// Creates an empty hasher bound to the given leaf and compression
+    /// parameters.
+    pub fn new(leaf_params: &H::Parameters, two_to_one_params: &C::Parameters) -> Self {
+        VariableLengthCRH {
+            leaf_params: leaf_params.clone(),
+            two_to_one_params: two_to_one_params.clone(),
+            buffer: Vec::new(),
+            _hashes: PhantomData,
+        }
+    }
+
+    /// Appends `data` to the input being hashed.
+    pub fn update(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Consumes the accumulated input and returns its digest.
+    ///
+    /// The final message block is zero-padded up to the block size, and a
+    /// dedicated length block encoding the total input length in bytes is
+    /// always appended before folding. Without the length suffix, inputs
+    /// differing only by trailing zero bytes in the last block — and the empty
+    /// input versus a single all-zero block — would collide; Merkle–Damgård
+    /// length-strengthening removes that ambiguity.
+    pub fn finalize(self) -> Result<H::Output, Error> {
+        // The length block carries the full 8-byte big-endian length; a block
+        // narrower than that would truncate it and reintroduce collisions for
+        // lengths differing by a multiple of `2^(8·BLOCK_SIZE)`.
+        if Self::BLOCK_SIZE < 8 {
+            return Err(Box::new(VariableLengthCRHError::BlockTooSmall(
+                Self::BLOCK_SIZE,
+            )));
+        }
+        let len = self.buffer.len() as u64;
+        let mut block = Vec::with_capacity(Self::BLOCK_SIZE);
+        let mut acc: Option<H::Output> = None;
+
+        let mut fold = |leaf_params: &H::Parameters,
+                        two_to_one_params: &C::Parameters,
+                        acc: &mut Option<H::Output>,
+                        block: &[u8]|
+         -> Result<(), Error> {
+            let digest = H::evaluate(leaf_params, block)?;
+            *acc = Some(match acc.take() {
+                None => digest,
+                Some(prev) => {
+                    let left = crate::to_bytes(&prev)?;
+                    let right = crate::to_bytes(&digest)?;
+                    C::compress(two_to_one_params, &left, &right)?
+                }
+            });
+            Ok(())
+        };
+
+        for chunk in self.buffer.chunks(Self::BLOCK_SIZE) {
+            block.clear();
+            block.extend_from_slice(chunk);
+            block.resize(Self::BLOCK_SIZE, 0u8);
+            fold(&self.leaf_params, &self.two_to_one_params, &mut acc, &block)?;
+        }
+
+        // Length-strengthening block: the big-endian input length, zero-padded.
+        block.clear();
+        block.extend_from_slice(&len.to_be_bytes());
+        block.resize(Self::BLOCK_SIZE, 0u8);
+        fold(&self.leaf_params, &self.two_to_one_params, &mut acc, &block)?;
+
+        Ok(acc.unwrap())
+    }
+}
+
+/// Errors raised by the variable-length hasher.
+#[derive(Debug)]
+pub enum VariableLengthCRHError {
+    /// The block size derived from `H::INPUT_SIZE_BITS` is too small to hold the
+    /// 8-byte big-endian length-strengthening suffix.
+    BlockTooSmall(usize),
+}
+
+impl Display for VariableLengthCRHError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            VariableLengthCRHError::BlockTooSmall(size) => write!(
+                f,
+                "block size ({} bytes) must be at least 8 bytes to hold the message length",
+                size
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VariableLengthCRHError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::crh::pedersen::{PedersenCRH, Window};
+    use algebra::curves::edwards_bls12::EdwardsProjective;
+    use rand::thread_rng;
+
+    #[derive(Clone)]
+    struct TestWindow;
+    impl Window for TestWindow {
+        const WINDOW_SIZE: usize = 8;
+        const NUM_WINDOWS: usize = 256;
+    }
+
+    type H = PedersenCRH<EdwardsProjective, TestWindow>;
+
+    fn hash(
+        leaf: &<H as FixedLengthCRH>::Parameters,
+        two_to_one: &<H as TwoToOneCRH>::Parameters,
+        data: &[u8],
+    ) -> <H as FixedLengthCRH>::Output {
+        let mut hasher = VariableLengthCRH::<H, H>::new(leaf, two_to_one);
+        hasher.update(data);
+        hasher.finalize().unwrap()
+    }
+
+    #[test]
+    fn length_strengthening_distinguishes_trailing_zeros() {
+        let rng = &mut thread_rng();
+        let leaf = <H as FixedLengthCRH>::setup(rng).unwrap();
+        let two_to_one = <H as TwoToOneCRH>::setup(rng).unwrap();
+
+        let a = hash(&leaf, &two_to_one, b"hello");
+        let mut padded = b"hello".to_vec();
+        padded.push(0u8);
+        let b = hash(&leaf, &two_to_one, &padded);
+        assert_ne!(a, b, "a trailing zero byte must change the digest");
+    }
+
+    #[test]
+    fn update_is_incremental() {
+        let rng = &mut thread_rng();
+        let leaf = <H as FixedLengthCRH>::setup(rng).unwrap();
+        let two_to_one = <H as TwoToOneCRH>::setup(rng).unwrap();
+
+        let whole = hash(&leaf, &two_to_one, b"streaming-input-data");
+
+        let mut hasher = VariableLengthCRH::<H, H>::new(&leaf, &two_to_one);
+        hasher.update(b"streaming-");
+        hasher.update(b"input-data");
+        assert_eq!(whole, hasher.finalize().unwrap());
+    }
+}
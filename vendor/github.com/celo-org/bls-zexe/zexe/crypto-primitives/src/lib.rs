@@ -0,0 +1,48 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+#[macro_use]
+extern crate std;
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+use algebra::bytes::ToBytes;
+
+pub mod crh;
+pub mod merkle_tree;
+
+/// Serializes a digest (or any [`ToBytes`]) to a freshly allocated byte vector.
+/// The io error is boxed explicitly: `?` would rely on `From<io::Error> for
+/// Error`, which only exists under `std`.
+pub(crate) fn to_bytes<D: ToBytes>(value: &D) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    value.write(&mut bytes).map_err(|e| Box::new(e) as Error)?;
+    Ok(bytes)
+}
+
+/// The boxed error type returned by the primitives in this crate. Boxing lets
+/// each backend surface its own failure mode without the traits fixing a
+/// concrete type. Under `std` it is `std::error::Error`; under `no_std` it is a
+/// minimal `core`-only shim with the same object-safe shape.
+pub type Error = Box<dyn self::error::Error + Send + Sync>;
+
+/// The error trait the boxed [`Error`] is erased to.
+///
+/// Note there is no blanket `From<E> for Error` under `no_std` — that impl is
+/// provided by the standard library only for `std::error::Error`. Backends
+/// therefore construct errors with `Box::new(..)` rather than `.into()`, which
+/// compiles under both configurations.
+pub mod error {
+    #[cfg(feature = "std")]
+    pub use std::error::Error;
+
+    #[cfg(not(feature = "std"))]
+    pub trait Error: core::fmt::Debug + core::fmt::Display {}
+    #[cfg(not(feature = "std"))]
+    impl<T: core::fmt::Debug + core::fmt::Display> Error for T {}
+}
@@ -0,0 +1,154 @@
+//! In-circuit membership verification for [`Path`](crate::merkle_tree::Path).
+//!
+//! This module supplies only the generic scaffolding: a `PathGadget` and the
+//! `check_membership` enforcement written against the `FixedLengthCRHGadget` /
+//! `TwoToOneCRHGadget` traits. It does not ship a concrete backend gadget, so a
+//! caller instantiates it with a hash gadget from `r1cs_std` (e.g. a Pedersen
+//! `FixedLengthCRHGadget`) to obtain a working in-SNARK path check.
+
+use algebra::Field;
+use core::borrow::Borrow;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::crh::constraints::{FixedLengthCRHGadget, TwoToOneCRHGadget};
+use crate::merkle_tree::{Config, Path};
+
+/// In-circuit analogue of `Path`: the sibling digest and left/right bit at each
+/// level, expressed as allocated gadget values.
+pub struct PathGadget<P, LeafGadget, TwoToOneGadget, ConstraintF>
+where
+    P: Config,
+    ConstraintF: Field,
+    LeafGadget: FixedLengthCRHGadget<P::LeafHash, ConstraintF>,
+    TwoToOneGadget: TwoToOneCRHGadget<P::TwoToOneHash, ConstraintF>,
+{
+    pub(crate) siblings: Vec<(TwoToOneGadget::OutputGadget, Boolean)>,
+    #[doc(hidden)]
+    pub(crate) _leaf: core::marker::PhantomData<LeafGadget>,
+}
+
+impl<P, LeafGadget, TwoToOneGadget, ConstraintF>
+    PathGadget<P, LeafGadget, TwoToOneGadget, ConstraintF>
+where
+    P: Config,
+    ConstraintF: Field,
+    LeafGadget: FixedLengthCRHGadget<P::LeafHash, ConstraintF>,
+    TwoToOneGadget: TwoToOneCRHGadget<
+        P::TwoToOneHash,
+        ConstraintF,
+        OutputGadget = LeafGadget::OutputGadget,
+    >,
+{
+    /// Enforces that `leaf` hashes up to `root` along this path. Mirrors
+    /// `Path::verify`: at every level the authenticated child is swapped to the
+    /// left or right of its sibling according to the allocated `is_left` bit.
+    pub fn check_membership<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        leaf_params: &LeafGadget::ParametersGadget,
+        two_to_one_params: &TwoToOneGadget::ParametersGadget,
+        root: &LeafGadget::OutputGadget,
+        leaf: &[UInt8],
+    ) -> Result<(), SynthesisError> {
+        let mut current =
+            LeafGadget::check_evaluation_gadget(cs.ns(|| "hash_leaf"), leaf_params, leaf)?;
+        for (i, (sibling, is_left)) in self.siblings.iter().enumerate() {
+            let mut cs = cs.ns(|| format!("level_{}", i));
+            let left = LeafGadget::OutputGadget::conditionally_select(
+                cs.ns(|| "select_left"),
+                is_left,
+                &current,
+                sibling,
+            )?;
+            let right = LeafGadget::OutputGadget::conditionally_select(
+                cs.ns(|| "select_right"),
+                is_left,
+                sibling,
+                &current,
+            )?;
+            let left_bytes = left.to_bytes(cs.ns(|| "left_bytes"))?;
+            let right_bytes = right.to_bytes(cs.ns(|| "right_bytes"))?;
+            current = TwoToOneGadget::check_compression_gadget(
+                cs.ns(|| "compress"),
+                two_to_one_params,
+                &left_bytes,
+                &right_bytes,
+            )?;
+        }
+        current.enforce_equal(cs.ns(|| "check_root"), root)
+    }
+}
+
+/// Allocates a native [`Path`] as a circuit witness (or public input), so a
+/// downstream circuit can obtain a `PathGadget` and call `check_membership`.
+/// Each level's sibling digest and left/right bit is allocated individually.
+impl<P, LeafGadget, TwoToOneGadget, ConstraintF> AllocGadget<Path<P>, ConstraintF>
+    for PathGadget<P, LeafGadget, TwoToOneGadget, ConstraintF>
+where
+    P: Config,
+    ConstraintF: Field,
+    LeafGadget: FixedLengthCRHGadget<P::LeafHash, ConstraintF>,
+    TwoToOneGadget: TwoToOneCRHGadget<
+        P::TwoToOneHash,
+        ConstraintF,
+        OutputGadget = LeafGadget::OutputGadget,
+    >,
+{
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<Path<P>>,
+    {
+        let value = value_gen()?;
+        let siblings = value.borrow().siblings.clone();
+        let mut allocated = Vec::with_capacity(siblings.len());
+        for (i, (sibling, is_left)) in siblings.into_iter().enumerate() {
+            let mut cs = cs.ns(|| format!("sibling_{}", i));
+            let sibling = TwoToOneGadget::OutputGadget::alloc(
+                cs.ns(|| "digest"),
+                || Ok(sibling),
+            )?;
+            let is_left = Boolean::alloc(cs.ns(|| "is_left"), || Ok(is_left))?;
+            allocated.push((sibling, is_left));
+        }
+        Ok(PathGadget {
+            siblings: allocated,
+            _leaf: core::marker::PhantomData,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<Path<P>>,
+    {
+        let value = value_gen()?;
+        let siblings = value.borrow().siblings.clone();
+        let mut allocated = Vec::with_capacity(siblings.len());
+        for (i, (sibling, is_left)) in siblings.into_iter().enumerate() {
+            let mut cs = cs.ns(|| format!("sibling_{}", i));
+            let sibling = TwoToOneGadget::OutputGadget::alloc_input(
+                cs.ns(|| "digest"),
+                || Ok(sibling),
+            )?;
+            let is_left = Boolean::alloc_input(cs.ns(|| "is_left"), || Ok(is_left))?;
+            allocated.push((sibling, is_left));
+        }
+        Ok(PathGadget {
+            siblings: allocated,
+            _leaf: core::marker::PhantomData,
+        })
+    }
+}
@@ -0,0 +1,229 @@
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use core::marker::PhantomData;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::crh::{FixedLengthCRH, TwoToOneCRH};
+use crate::Error;
+
+/// Errors raised by the Merkle tree on malformed caller input.
+#[derive(Debug)]
+pub enum MerkleTreeError {
+    /// The number of leaves was not a non-zero power of two.
+    NumberOfLeavesNotPowerOfTwo(usize),
+    /// A proof was requested for a leaf index outside the tree.
+    LeafIndexOutOfRange { index: usize, num_leaves: usize },
+}
+
+impl Display for MerkleTreeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            MerkleTreeError::NumberOfLeavesNotPowerOfTwo(n) => {
+                write!(f, "the number of leaves ({}) must be a power of two", n)
+            }
+            MerkleTreeError::LeafIndexOutOfRange { index, num_leaves } => write!(
+                f,
+                "leaf index {} is out of range for a tree with {} leaves",
+                index, num_leaves
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MerkleTreeError {}
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+#[cfg(feature = "r1cs")]
+pub use constraints::*;
+
+/// Binds the two hashes used by a `MerkleTree`: `LeafHash` hashes the raw
+/// leaves and `TwoToOneHash` compresses a pair of child digests into the
+/// digest of their parent. The two hashes must agree on the node digest type.
+pub trait Config {
+    type LeafHash: FixedLengthCRH;
+    type TwoToOneHash: TwoToOneCRH<
+        Output = <Self::LeafHash as FixedLengthCRH>::Output,
+    >;
+}
+
+type LeafParam<P> = <<P as Config>::LeafHash as FixedLengthCRH>::Parameters;
+type TwoToOneParam<P> = <<P as Config>::TwoToOneHash as TwoToOneCRH>::Parameters;
+type Digest<P> = <<P as Config>::LeafHash as FixedLengthCRH>::Output;
+
+/// A membership proof for a single leaf: the sibling digest at every level on
+/// the path from the leaf up to (but excluding) the root, ordered leaf-first.
+#[derive(Clone)]
+pub struct Path<P: Config> {
+    /// For each level, the sibling digest and whether the authenticated node is
+    /// the left child at that level.
+    pub(crate) siblings: Vec<(Digest<P>, bool)>,
+}
+
+/// A binary Merkle tree over a power-of-two number of leaves.
+pub struct MerkleTree<P: Config> {
+    /// The nodes of the tree stored level by level, root at index 0.
+    nodes: Vec<Vec<Digest<P>>>,
+    _config: PhantomData<P>,
+}
+
+impl<P: Config> MerkleTree<P> {
+    /// Builds the tree by hashing every leaf and folding pairs upward with the
+    /// two-to-one hash. The number of leaves must be a non-zero power of two.
+    pub fn new(
+        leaf_params: &LeafParam<P>,
+        two_to_one_params: &TwoToOneParam<P>,
+        leaves: &[&[u8]],
+    ) -> Result<Self, Error> {
+        if leaves.is_empty() || !leaves.len().is_power_of_two() {
+            return Err(Box::new(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(
+                leaves.len(),
+            )));
+        }
+
+        let mut leaf_digests = Vec::with_capacity(leaves.len());
+        for leaf in leaves {
+            leaf_digests.push(P::LeafHash::evaluate(leaf_params, leaf)?);
+        }
+
+        let mut nodes = vec![leaf_digests];
+        while nodes.last().unwrap().len() > 1 {
+            let level = nodes.last().unwrap();
+            let mut parents = Vec::with_capacity(level.len() / 2);
+            for pair in level.chunks(2) {
+                let left = crate::to_bytes(&pair[0])?;
+                let right = crate::to_bytes(&pair[1])?;
+                parents.push(P::TwoToOneHash::compress(two_to_one_params, &left, &right)?);
+            }
+            nodes.push(parents);
+        }
+        // Store root-first so that `nodes[0]` is the root level.
+        nodes.reverse();
+
+        Ok(MerkleTree {
+            nodes,
+            _config: PhantomData,
+        })
+    }
+
+    /// The root digest of the tree.
+    pub fn root(&self) -> Digest<P> {
+        self.nodes[0][0].clone()
+    }
+
+    /// Produces a membership proof for the leaf at `index`.
+    pub fn generate_proof(&self, index: usize) -> Result<Path<P>, Error> {
+        let leaf_level = self.nodes.len() - 1;
+        let num_leaves = self.nodes[leaf_level].len();
+        if index >= num_leaves {
+            return Err(Box::new(MerkleTreeError::LeafIndexOutOfRange {
+                index,
+                num_leaves,
+            }));
+        }
+
+        let mut siblings = Vec::with_capacity(leaf_level);
+        let mut position = index;
+        for level in (1..self.nodes.len()).rev() {
+            let is_left = position % 2 == 0;
+            let sibling = position ^ 1;
+            siblings.push((self.nodes[level][sibling].clone(), is_left));
+            position /= 2;
+        }
+
+        Ok(Path { siblings })
+    }
+}
+
+impl<P: Config> Path<P> {
+    /// Re-derives the root from `leaf` and checks it against `root`.
+    ///
+    /// The hash parameters are threaded in by the caller rather than stored in
+    /// the proof: a `Path` carries only its sibling list, keeping it compact and
+    /// cheap to clone and persist instead of embedding the full generator tables.
+    pub fn verify(
+        &self,
+        leaf_params: &LeafParam<P>,
+        two_to_one_params: &TwoToOneParam<P>,
+        root: &Digest<P>,
+        leaf: &[u8],
+    ) -> Result<bool, Error> {
+        let mut current = P::LeafHash::evaluate(leaf_params, leaf)?;
+        for (sibling, is_left) in &self.siblings {
+            let current_bytes = crate::to_bytes(&current)?;
+            let sibling_bytes = crate::to_bytes(sibling)?;
+            current = if *is_left {
+                P::TwoToOneHash::compress(two_to_one_params, &current_bytes, &sibling_bytes)?
+            } else {
+                P::TwoToOneHash::compress(two_to_one_params, &sibling_bytes, &current_bytes)?
+            };
+        }
+        Ok(&current == root)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::crh::pedersen::{PedersenCRH, Window};
+    use algebra::curves::edwards_bls12::EdwardsProjective;
+    use rand::thread_rng;
+
+    // Wide enough windows that two serialized node digests fit in a single
+    // two-to-one input.
+    #[derive(Clone)]
+    struct TestWindow;
+    impl Window for TestWindow {
+        const WINDOW_SIZE: usize = 8;
+        const NUM_WINDOWS: usize = 256;
+    }
+
+    type H = PedersenCRH<EdwardsProjective, TestWindow>;
+
+    struct TestConfig;
+    impl Config for TestConfig {
+        type LeafHash = H;
+        type TwoToOneHash = H;
+    }
+
+    #[test]
+    fn proof_round_trip_and_tamper() {
+        let rng = &mut thread_rng();
+        let leaf_params = <H as FixedLengthCRH>::setup(rng).unwrap();
+        let two_to_one_params = <H as TwoToOneCRH>::setup(rng).unwrap();
+
+        let leaves: [&[u8]; 4] = [&[1u8; 8], &[2u8; 8], &[3u8; 8], &[4u8; 8]];
+        let tree =
+            MerkleTree::<TestConfig>::new(&leaf_params, &two_to_one_params, &leaves).unwrap();
+        let root = tree.root();
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.generate_proof(index).unwrap();
+            assert!(proof
+                .verify(&leaf_params, &two_to_one_params, &root, leaf)
+                .unwrap());
+            // The same path must reject any other leaf value.
+            assert!(!proof
+                .verify(&leaf_params, &two_to_one_params, &root, &[0xFFu8; 8])
+                .unwrap());
+        }
+    }
+
+    #[test]
+    fn rejects_non_power_of_two_and_bad_index() {
+        let rng = &mut thread_rng();
+        let leaf_params = <H as FixedLengthCRH>::setup(rng).unwrap();
+        let two_to_one_params = <H as TwoToOneCRH>::setup(rng).unwrap();
+
+        let three: [&[u8]; 3] = [&[1u8; 8], &[2u8; 8], &[3u8; 8]];
+        assert!(MerkleTree::<TestConfig>::new(&leaf_params, &two_to_one_params, &three).is_err());
+
+        let two: [&[u8]; 2] = [&[1u8; 8], &[2u8; 8]];
+        let tree = MerkleTree::<TestConfig>::new(&leaf_params, &two_to_one_params, &two).unwrap();
+        assert!(tree.generate_proof(2).is_err());
+    }
+}